@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use rand::Rng;
+
+use crate::{Ball, BALL_RADIUS, WINDOW_HIGHT};
+
+/// Drives a paddle without keyboard input. `max_speed` caps how fast it can
+/// chase the predicted intercept, `reaction_delay` is how often (in
+/// seconds) it's allowed to re-read the ball and commit to a new target,
+/// and `error_margin` jitters that target so the AI isn't a perfect wall.
+#[derive(Component, Clone)]
+pub struct AiController {
+    pub max_speed: f32,
+    pub reaction_delay: f32,
+    pub error_margin: f32,
+    target_y: f32,
+    timer: Timer,
+}
+
+impl AiController {
+    pub fn new(max_speed: f32, reaction_delay: f32, error_margin: f32) -> Self {
+        let reaction_delay = reaction_delay.max(0.01);
+        let mut timer = Timer::from_seconds(reaction_delay, TimerMode::Repeating);
+        // Start "already elapsed" so the AI picks a target on its very first tick
+        // instead of sitting still for one full reaction_delay.
+        timer.set_elapsed(Duration::from_secs_f32(reaction_delay));
+        Self {
+            max_speed,
+            reaction_delay,
+            error_margin,
+            target_y: 0.,
+            timer,
+        }
+    }
+}
+
+impl Default for AiController {
+    fn default() -> Self {
+        Self::new(120., 0.15, 20.)
+    }
+}
+
+pub fn ai_move_paddle(
+    time: Res<Time>,
+    ball: Query<(&Transform, &Velocity), With<Ball>>,
+    mut paddles: Query<(&mut Transform, &mut AiController), Without<Ball>>,
+) {
+    let Ok((ball_transform, ball_velocity)) = ball.get_single() else {
+        return;
+    };
+
+    for (mut paddle_transform, mut ai) in &mut paddles {
+        ai.timer.tick(time.delta());
+        if ai.timer.just_finished() {
+            let jitter = rand::thread_rng().gen_range(-ai.error_margin..=ai.error_margin);
+            ai.target_y = predict_intercept_y(
+                ball_transform.translation,
+                ball_velocity.linvel,
+                paddle_transform.translation.x,
+            ) + jitter;
+        }
+
+        let max_step = ai.max_speed * time.delta_seconds();
+        let delta = (ai.target_y - paddle_transform.translation.y).clamp(-max_step, max_step);
+        paddle_transform.translation.y += delta;
+        paddle_transform.translation.y = paddle_transform
+            .translation
+            .y
+            .clamp((-WINDOW_HIGHT / 2.) + 75., (WINDOW_HIGHT / 2.) - 75.);
+    }
+}
+
+/// Extrapolates the ball's straight-line motion to `paddle_x`, reflecting
+/// it off the top/bottom borders as many times as needed, and returns the
+/// y it will arrive at.
+fn predict_intercept_y(ball_translation: Vec3, ball_velocity: Vec2, paddle_x: f32) -> f32 {
+    if ball_velocity.x.abs() < f32::EPSILON {
+        return ball_translation.y;
+    }
+
+    let t = (paddle_x - ball_translation.x) / ball_velocity.x;
+    if t <= 0. {
+        // Ball is heading away from this paddle; no point chasing it yet.
+        return ball_translation.y;
+    }
+
+    let unbounded_y = ball_translation.y + ball_velocity.y * t;
+    reflect_within_borders(unbounded_y, WINDOW_HIGHT / 2. - BALL_RADIUS)
+}
+
+/// Folds `y` into `[-range, range]` as if it had bounced off walls placed
+/// at `+range`/`-range`, the way the ball bounces off the top/bottom
+/// borders.
+fn reflect_within_borders(y: f32, range: f32) -> f32 {
+    let period = 4. * range;
+    let mut folded = (y + range) % period;
+    if folded < 0. {
+        folded += period;
+    }
+    if folded > 2. * range {
+        folded = period - folded;
+    }
+    folded - range
+}
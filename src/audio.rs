@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::CollidingEntities;
+
+use crate::Ball;
+
+/// Tags the fixed top/bottom colliders spawned by `spawn_border`, so the
+/// wall-bounce sound can tell them apart from paddles and goals.
+#[derive(Component)]
+pub struct Border;
+
+/// `Handle<AudioSource>`s loaded once at startup, so every system that
+/// wants to play a sound effect just clones a handle out of this resource
+/// instead of hitting the asset server again.
+#[derive(Resource)]
+pub struct GameAudio {
+    pub paddle_hit: Handle<AudioSource>,
+    pub wall_bounce: Handle<AudioSource>,
+    pub score: Handle<AudioSource>,
+}
+
+/// Consecutive paddle hits since the last reset. Used to pitch the hit
+/// sound up slightly for longer rallies. Mutated from inside `GgrsSchedule`
+/// in online play, so it must be registered with `rollback_resource_with_clone`
+/// alongside `Score`/`MatchRng` or a rollback resimulates it against stale
+/// state instead of the snapshot for that frame.
+#[derive(Resource, Default, Clone)]
+pub struct RallyCount(pub u32);
+
+const PITCH_STEP: f32 = 0.04;
+const MAX_PITCH: f32 = 1.8;
+
+impl RallyCount {
+    pub fn pitch(&self) -> f32 {
+        (1. + PITCH_STEP * self.0 as f32).min(MAX_PITCH)
+    }
+
+    pub fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+pub fn load_audio(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameAudio {
+        paddle_hit: asset_server.load("sounds/paddle_hit.ogg"),
+        wall_bounce: asset_server.load("sounds/wall_bounce.ogg"),
+        score: asset_server.load("sounds/score.ogg"),
+    });
+    commands.insert_resource(RallyCount::default());
+    commands.init_resource::<AudioCues>();
+}
+
+/// What sound effects to play, recorded by the deterministic gameplay
+/// systems instead of spawning `AudioBundle`s directly. Several of those
+/// systems (`ball_hit`, `wall_bounce`, `score`) are chained into
+/// `GgrsSchedule` for online play, which can run several ticks in one real
+/// frame when it resimulates past frames on a rollback. `play_audio_cues`
+/// runs outside `GgrsSchedule`, once per real frame, and drains whatever
+/// the ticks run since the previous real frame recorded, so each sound
+/// plays at most once per real frame. The cues are not rolled back: a
+/// resimulated tick that hits, bounces or scores sets its cue like any
+/// other tick.
+#[derive(Resource, Default)]
+pub struct AudioCues {
+    pub paddle_hit_pitch: Option<f32>,
+    pub wall_bounce: bool,
+    pub score: bool,
+}
+
+pub fn wall_bounce(
+    mut cues: ResMut<AudioCues>,
+    borders: Query<Entity, With<Border>>,
+    balls: Query<&CollidingEntities, With<Ball>>,
+) {
+    for hits in &balls {
+        for hit in hits.iter() {
+            if borders.get(hit).is_ok() {
+                cues.wall_bounce = true;
+                return;
+            }
+        }
+    }
+}
+
+pub fn play_audio_cues(mut commands: Commands, audio: Res<GameAudio>, mut cues: ResMut<AudioCues>) {
+    if let Some(pitch) = cues.paddle_hit_pitch.take() {
+        commands.spawn(AudioBundle {
+            source: audio.paddle_hit.clone(),
+            settings: PlaybackSettings::DESPAWN.with_speed(pitch),
+        });
+    }
+    if std::mem::take(&mut cues.wall_bounce) {
+        commands.spawn(AudioBundle {
+            source: audio.wall_bounce.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+    if std::mem::take(&mut cues.score) {
+        commands.spawn(AudioBundle {
+            source: audio.score.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+}
@@ -0,0 +1,196 @@
+//! Single-player Breakout variant: a grid of destructible `Brick`s sits in
+//! the upper half of the field, the ball bounces around the existing
+//! Rapier walls, and a horizontal paddle at the bottom is all that stands
+//! between a miss and `GameEvents::ResetBall`. Reuses `spawn_border`,
+//! `Ball`, `Player::Player1`, and the menu/win state machine as-is.
+
+use bevy::{
+    color::{
+        palettes::css::{BLUE, ORANGE, PURPLE, YELLOW},
+        Srgba,
+    },
+    prelude::*,
+};
+use bevy_rapier2d::prelude::*;
+
+use crate::audio::Border;
+use crate::state::{AppState, InMatch, MatchResult};
+use crate::{Ball, GameEvents, Player, WINDOW_HIGHT, WINDOW_WIDTH};
+
+const BRICK_ROWS: i32 = 5;
+const BRICK_COLS: i32 = 10;
+const BRICK_WIDTH: f32 = 100.;
+const BRICK_HEIGHT: f32 = 30.;
+const BRICK_GAP: f32 = 8.;
+const BRICK_TOP_MARGIN: f32 = 60.;
+const BRICK_ROW_COLORS: [Srgba; 5] = [ORANGE, YELLOW, BLUE, PURPLE, ORANGE];
+
+const PADDLE_WIDTH: f32 = 150.;
+const PADDLE_SPEED: f32 = 250.;
+
+const BALL_LAUNCH_VELOCITY: Vec2 = Vec2::new(140., 220.);
+
+#[derive(Component)]
+pub struct Brick;
+
+/// Marks the bottom `Border` (see `convert_bottom_border_to_lose_goal`) as
+/// a miss rather than a wall: touching it resets the ball instead of
+/// bouncing it.
+#[derive(Component)]
+pub struct LoseGoal;
+
+#[derive(Component)]
+pub struct BreakoutPaddle {
+    move_left: KeyCode,
+    move_right: KeyCode,
+}
+
+pub fn spawn_bricks(mut commands: Commands) {
+    let total_width = BRICK_COLS as f32 * (BRICK_WIDTH + BRICK_GAP) - BRICK_GAP;
+    let start_x = -total_width / 2. + BRICK_WIDTH / 2.;
+    let start_y = WINDOW_HIGHT / 2. - BRICK_TOP_MARGIN - BRICK_HEIGHT / 2.;
+
+    for row in 0..BRICK_ROWS {
+        for col in 0..BRICK_COLS {
+            let x = start_x + col as f32 * (BRICK_WIDTH + BRICK_GAP);
+            let y = start_y - row as f32 * (BRICK_HEIGHT + BRICK_GAP);
+
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_translation(Vec3::new(x, y, 0.)),
+                    sprite: Sprite {
+                        color: BRICK_ROW_COLORS[row as usize % BRICK_ROW_COLORS.len()].into(),
+                        custom_size: Some(Vec2::new(BRICK_WIDTH, BRICK_HEIGHT)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Brick,
+                RigidBody::Fixed,
+                Collider::cuboid(BRICK_WIDTH / 2., BRICK_HEIGHT / 2.),
+                Sensor,
+                InMatch,
+            ));
+        }
+    }
+}
+
+pub fn spawn_breakout_paddle(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(Vec3::new(0., -WINDOW_HIGHT / 2. + 40., 0.)),
+            sprite: Sprite {
+                color: Player::Player1.get_color(),
+                custom_size: Some(Vec2::new(PADDLE_WIDTH, 10.)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        BreakoutPaddle {
+            move_left: KeyCode::ArrowLeft,
+            move_right: KeyCode::ArrowRight,
+        },
+        Player::Player1,
+        RigidBody::KinematicPositionBased,
+        Collider::cuboid(PADDLE_WIDTH / 2., 5.),
+        InMatch,
+    ));
+}
+
+/// Turns the physical bottom wall `spawn_border` already spawns into a
+/// sensor, so a missed ball resets instead of bouncing back up forever,
+/// and turns the side goal sensors into solid walls the ball bounces off.
+pub fn convert_bottom_border_to_lose_goal(
+    mut commands: Commands,
+    borders: Query<(Entity, &Transform), With<Border>>,
+    side_goals: Query<Entity, (With<Player>, With<Sensor>)>,
+) {
+    for entity in &side_goals {
+        commands.entity(entity).remove::<Sensor>();
+    }
+    for (entity, transform) in &borders {
+        if transform.translation.y < 0. {
+            commands
+                .entity(entity)
+                .insert((Sensor, LoseGoal))
+                .remove::<Border>();
+        }
+    }
+}
+
+pub fn launch_ball(mut balls: Query<&mut Velocity, With<Ball>>) {
+    for mut velocity in &mut balls {
+        *velocity = Velocity::linear(BALL_LAUNCH_VELOCITY);
+    }
+}
+
+pub fn move_breakout_paddle(
+    mut paddles: Query<(&mut Transform, &BreakoutPaddle)>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    for (mut pos, settings) in &mut paddles {
+        if input.pressed(settings.move_left) {
+            pos.translation.x -= PADDLE_SPEED * time.delta_seconds();
+        }
+        if input.pressed(settings.move_right) {
+            pos.translation.x += PADDLE_SPEED * time.delta_seconds();
+        }
+        pos.translation.x = pos
+            .translation
+            .x
+            .clamp((-WINDOW_WIDTH / 2.) + 75., (WINDOW_WIDTH / 2.) - 75.);
+    }
+}
+
+pub fn destroy_bricks(
+    mut commands: Commands,
+    mut game_events: EventWriter<GameEvents>,
+    bricks: Query<Entity, With<Brick>>,
+    balls: Query<&CollidingEntities, With<Ball>>,
+) {
+    for hits in &balls {
+        for hit in hits.iter() {
+            if bricks.get(hit).is_ok() {
+                commands.entity(hit).despawn();
+                game_events.send(GameEvents::GainPoint(Player::Player1));
+                return;
+            }
+        }
+    }
+}
+
+pub fn detect_lose(
+    balls: Query<&CollidingEntities, With<Ball>>,
+    goals: Query<Entity, With<LoseGoal>>,
+    mut game_events: EventWriter<GameEvents>,
+) {
+    for hits in &balls {
+        for hit in hits.iter() {
+            if goals.get(hit).is_ok() {
+                game_events.send(GameEvents::ResetBall(Player::Player1));
+                return;
+            }
+        }
+    }
+}
+
+/// Mirrors `state::check_win`: once the last brick is gone the level is
+/// cleared, so freeze the ball and hand off to the same `GameOver` screen
+/// a regular match win uses.
+pub fn check_cleared(
+    bricks: Query<(), With<Brick>>,
+    mut balls: Query<&mut Velocity, With<Ball>>,
+    mut match_result: ResMut<MatchResult>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if bricks.iter().next().is_some() {
+        return;
+    }
+
+    for mut velocity in &mut balls {
+        *velocity = Velocity::zero();
+    }
+    match_result.0 = Some(Player::Player1);
+    next_state.set(AppState::GameOver);
+}
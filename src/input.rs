@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_ggrs::LocalInputs;
+
+use crate::network::GgrsConfig;
+
+pub const INPUT_UP: u8 = 1 << 0;
+pub const INPUT_DOWN: u8 = 1 << 1;
+pub const INPUT_RESET: u8 = 1 << 2;
+
+/// Per-frame input sent through GGRS. Has to be `Copy`/`Pod` so it can be
+/// serialized and replayed during a rollback.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct BoxInput {
+    pub buttons: u8,
+}
+
+impl BoxInput {
+    pub fn up(&self) -> bool {
+        self.buttons & INPUT_UP != 0
+    }
+
+    pub fn down(&self) -> bool {
+        self.buttons & INPUT_DOWN != 0
+    }
+
+    pub fn reset(&self) -> bool {
+        self.buttons & INPUT_RESET != 0
+    }
+}
+
+/// Samples the keyboard for every locally controlled handle and stashes the
+/// result as a `LocalInputs` resource, which `bevy_ggrs` picks up right
+/// before it advances the rollback schedule.
+pub fn read_local_inputs(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    local_players: Res<bevy_ggrs::LocalPlayers>,
+) {
+    let mut local_inputs = HashMap::new();
+
+    for handle in &local_players.0 {
+        let mut buttons = 0u8;
+
+        if keys.pressed(KeyCode::KeyW) || keys.pressed(KeyCode::ArrowUp) {
+            buttons |= INPUT_UP;
+        }
+        if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
+            buttons |= INPUT_DOWN;
+        }
+        if keys.just_pressed(KeyCode::Space) {
+            buttons |= INPUT_RESET;
+        }
+
+        local_inputs.insert(*handle, BoxInput { buttons });
+    }
+
+    commands.insert_resource(LocalInputs::<GgrsConfig>(local_inputs));
+}
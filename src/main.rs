@@ -1,18 +1,79 @@
+mod ai;
+mod audio;
+mod brick;
+mod input;
+mod network;
+mod rollback;
+mod state;
+
 use bevy::{
     color::palettes::css::{DARK_GRAY, GREEN, RED},
     prelude::*,
     utils::HashMap,
     window::WindowResolution,
 };
+use bevy_ggrs::{GgrsApp, GgrsPlugin, GgrsSchedule, ReadInputs};
 use bevy_rapier2d::prelude::*;
-use rand::Rng;
+use clap::Parser;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use ai::{ai_move_paddle, AiController};
+use audio::{load_audio, play_audio_cues, wall_bounce, AudioCues, Border, RallyCount};
+use brick::{
+    check_cleared, convert_bottom_border_to_lose_goal, destroy_bricks, detect_lose, launch_ball,
+    move_breakout_paddle, spawn_breakout_paddle, spawn_bricks,
+};
+use input::read_local_inputs;
+use network::{GgrsConfig, NetOpt};
+use rollback::{assign_rollback, detect_reset_rollback, move_paddle_rollback};
+use state::{
+    check_win, despawn_game_over, despawn_match, despawn_menu, handle_menu_input,
+    handle_restart_input, reset_score, spawn_game_over, spawn_menu, AppState, InMatch, MatchResult,
+    WinScore,
+};
+
+/// The mode picked from the start menu: two-player Pong, Pong against the
+/// tracking AI, or the single-player Breakout variant.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default)]
+enum GameMode {
+    #[default]
+    HumanVsHuman,
+    HumanVsAi,
+    Breakout,
+}
+
+fn in_breakout_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Breakout
+}
+
+fn not_breakout_mode(mode: Res<GameMode>) -> bool {
+    *mode != GameMode::Breakout
+}
 
 const WINDOW_WIDTH: f32 = 1280.;
 const WINDOW_HIGHT: f32 = 720.;
 const BALL_RADIUS: f32 = 25.;
 
+/// Seeded stand-in for `rand::thread_rng()`. Registered as a rollback
+/// resource so every peer replays the same "coin flip" on a reset, instead
+/// of each machine picking its own random winner.
+#[derive(Resource, Clone)]
+struct MatchRng(StdRng);
+
 fn main() {
+    let opt = NetOpt::parse();
+    // `vs_ai` is a local-only option: `assign_rollback` only tags `Paddle`
+    // entities for rollback and no `ai_move_paddle` system runs in
+    // `GgrsSchedule`, so an AI-controlled Player2 would just sit untracked
+    // and motionless in an online match.
+    let mode = if opt.vs_ai && !opt.is_online() {
+        GameMode::HumanVsAi
+    } else {
+        GameMode::HumanVsHuman
+    };
+
     let mut app = App::new();
+    app.insert_resource(mode);
     app.add_plugins(DefaultPlugins.set(WindowPlugin {
         primary_window: Some(Window {
             resolution: WindowResolution::new(WINDOW_WIDTH, WINDOW_HIGHT),
@@ -23,27 +84,131 @@ fn main() {
     }));
 
     app.init_resource::<Score>();
+    app.init_state::<AppState>();
+    app.init_resource::<WinScore>();
+    app.init_resource::<MatchResult>();
 
     app.insert_resource(RapierConfiguration {
         gravity: Vec2::ZERO,
         ..RapierConfiguration::new(1.)
     });
-    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+    // Online play steps physics itself, once per resimulated GgrsSchedule
+    // tick, below; the default per-real-frame scheduling would leave the
+    // Rapier-simulated ball out of step with everything else the rollback
+    // replays.
+    app.add_plugins(
+        RapierPhysicsPlugin::<NoUserData>::default().with_default_system_setup(!opt.is_online()),
+    );
     #[cfg(debug_assertions)]
     app.add_plugins(RapierDebugRenderPlugin::default());
     app.add_event::<GameEvents>();
-    app.add_systems(
-        Startup,
-        (
-            spawn_score,
-            spawn_camera,
-            spawn_players,
-            spawn_ball,
-            spawn_border,
-        ),
-    );
-    app.add_systems(Update, (move_paddle, detect_reset, ball_hit));
-    app.add_systems(PostUpdate, (reset_ball, score));
+
+    app.add_systems(Startup, (load_audio, spawn_score, spawn_camera));
+
+    if opt.is_online() {
+        app.add_plugins(GgrsPlugin::<GgrsConfig>::default());
+        app.set_rollback_schedule_fps(network::FPS);
+        app.rollback_component_with_copy::<Transform>();
+        app.rollback_component_with_copy::<Velocity>();
+        app.rollback_resource_with_clone::<Score>();
+        app.rollback_resource_with_clone::<MatchRng>();
+        app.rollback_resource_with_clone::<RallyCount>();
+
+        app.insert_resource(MatchRng(StdRng::seed_from_u64(0)));
+        app.insert_resource(network::start_session(&opt));
+        app.insert_resource(opt);
+
+        // Online sessions skip the menu entirely: both players are already
+        // committed to the match by virtue of having started it.
+        app.add_systems(Startup, (spawn_players, spawn_ball, spawn_border));
+        app.add_systems(
+            Startup,
+            assign_rollback.after(spawn_players).after(spawn_ball),
+        );
+        app.add_systems(ReadInputs, read_local_inputs);
+        app.add_systems(
+            GgrsSchedule,
+            (
+                move_paddle_rollback,
+                detect_reset_rollback,
+                // Rapier's own system setup is disabled above, so step it
+                // explicitly here: once per resimulated tick rather than
+                // once per real frame, so the ball replays identically to
+                // the paddles and inputs around it.
+                (
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::SyncBackend),
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::StepSimulation),
+                    RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::Writeback),
+                )
+                    .chain(),
+                ball_hit,
+                wall_bounce,
+                reset_ball,
+                score,
+            )
+                .chain(),
+        );
+        // Outside GgrsSchedule and so never resimulated: drains whatever
+        // AudioCues the ticks of this real frame recorded, so a sound plays
+        // at most once per real frame however many ticks were run.
+        app.add_systems(PostUpdate, play_audio_cues);
+    } else {
+        app.insert_resource(MatchRng(StdRng::from_entropy()));
+
+        app.add_systems(OnEnter(AppState::Menu), spawn_menu);
+        app.add_systems(OnExit(AppState::Menu), despawn_menu);
+        app.add_systems(Update, handle_menu_input.run_if(in_state(AppState::Menu)));
+
+        app.add_systems(
+            OnEnter(AppState::Playing),
+            (
+                spawn_players.run_if(not_breakout_mode),
+                spawn_bricks.run_if(in_breakout_mode),
+                spawn_breakout_paddle.run_if(in_breakout_mode),
+                spawn_ball,
+                spawn_border,
+                convert_bottom_border_to_lose_goal.run_if(in_breakout_mode),
+                launch_ball.run_if(in_breakout_mode),
+                reset_score,
+            )
+                .chain(),
+        );
+        app.add_systems(OnExit(AppState::Playing), despawn_match);
+        app.add_systems(
+            Update,
+            (
+                move_paddle.run_if(not_breakout_mode),
+                ai_move_paddle.run_if(not_breakout_mode),
+                detect_reset.run_if(not_breakout_mode),
+                move_breakout_paddle.run_if(in_breakout_mode),
+                destroy_bricks.run_if(in_breakout_mode),
+                detect_lose.run_if(in_breakout_mode),
+                ball_hit,
+                wall_bounce,
+            )
+                .run_if(in_state(AppState::Playing)),
+        );
+        app.add_systems(
+            PostUpdate,
+            (
+                reset_ball,
+                score,
+                play_audio_cues,
+                check_win.run_if(not_breakout_mode),
+                check_cleared.run_if(in_breakout_mode),
+            )
+                .chain()
+                .run_if(in_state(AppState::Playing)),
+        );
+
+        app.add_systems(OnEnter(AppState::GameOver), spawn_game_over);
+        app.add_systems(OnExit(AppState::GameOver), despawn_game_over);
+        app.add_systems(
+            Update,
+            handle_restart_input.run_if(in_state(AppState::GameOver)),
+        );
+    }
+
     app.run();
 }
 
@@ -87,6 +252,8 @@ fn spawn_border(mut commands: Commands) {
         },
         RigidBody::Fixed,
         Collider::cuboid(WINDOW_WIDTH / 2., 3.),
+        Border,
+        InMatch,
     ));
     commands.spawn((
         SpatialBundle {
@@ -95,6 +262,8 @@ fn spawn_border(mut commands: Commands) {
         },
         RigidBody::Fixed,
         Collider::cuboid(WINDOW_WIDTH / 2., 3.),
+        Border,
+        InMatch,
     ));
 
     commands.spawn((
@@ -106,6 +275,7 @@ fn spawn_border(mut commands: Commands) {
         Collider::cuboid(3., WINDOW_HIGHT / 2.),
         Player::Player1,
         Sensor,
+        InMatch,
     ));
 
     commands.spawn((
@@ -117,10 +287,11 @@ fn spawn_border(mut commands: Commands) {
         Collider::cuboid(3., WINDOW_HIGHT / 2.),
         Player::Player2,
         Sensor,
+        InMatch,
     ));
 }
 
-fn spawn_players(mut commands: Commands) {
+fn spawn_players(mut commands: Commands, mode: Res<GameMode>) {
     commands.spawn((
         SpriteBundle {
             transform: Transform::from_translation(Vec3::new(-WINDOW_WIDTH / 2. + 20., 0., 0.)),
@@ -138,26 +309,42 @@ fn spawn_players(mut commands: Commands) {
         Player::Player1,
         RigidBody::KinematicPositionBased,
         Collider::cuboid(5., 75.),
+        InMatch,
     ));
 
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_translation(Vec3::new(WINDOW_WIDTH / 2. - 20., 0., 0.)),
-            sprite: Sprite {
-                color: Player::Player2.get_color(),
-                custom_size: Some(Vec2::new(10., 150.)),
+    let player_two = commands
+        .spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(Vec3::new(WINDOW_WIDTH / 2. - 20., 0., 0.)),
+                sprite: Sprite {
+                    color: Player::Player2.get_color(),
+                    custom_size: Some(Vec2::new(10., 150.)),
+                    ..Default::default()
+                },
                 ..Default::default()
             },
-            ..Default::default()
-        },
-        Paddle {
-            move_up: KeyCode::ArrowUp,
-            move_down: KeyCode::ArrowDown,
-        },
-        Player::Player2,
-        RigidBody::KinematicPositionBased,
-        Collider::cuboid(5., 75.),
-    ));
+            Player::Player2,
+            RigidBody::KinematicPositionBased,
+            Collider::cuboid(5., 75.),
+            InMatch,
+        ))
+        .id();
+
+    match *mode {
+        GameMode::HumanVsHuman => {
+            commands.entity(player_two).insert(Paddle {
+                move_up: KeyCode::ArrowUp,
+                move_down: KeyCode::ArrowDown,
+            });
+        }
+        GameMode::HumanVsAi => {
+            commands.entity(player_two).insert(AiController::default());
+        }
+        // Breakout never calls `spawn_players` (see `not_breakout_mode`
+        // guards in `main`); kept so the match stays exhaustive as
+        // `GameMode` grows.
+        GameMode::Breakout => {}
+    }
 }
 
 fn move_paddle(
@@ -206,20 +393,43 @@ fn spawn_ball(mut commands: Commands, asset_server: Res<AssetServer>) {
         Collider::ball(BALL_RADIUS),
         Velocity::linear(Vec2::new(100., 0.)),
         Restitution {
-            coefficient: 1.1,
+            coefficient: 1.,
             combine_rule: CoefficientCombineRule::Max,
         },
+        InMatch,
     ));
 }
 
+/// Max deflection off dead-center contact, matching classic Pong: hitting
+/// near the paddle's edge sends the ball off steeply instead of straight
+/// back.
+const MAX_BOUNCE_ANGLE: f32 = 60. * std::f32::consts::PI / 180.;
+const MAX_BALL_SPEED: f32 = 600.;
+const BOUNCE_SPEED_GAIN: f32 = 1.05;
+
 fn ball_hit(
-    paddles: Query<&Player, With<Paddle>>,
-    mut balls: Query<(&CollidingEntities, &mut Sprite), With<Ball>>,
+    mut cues: ResMut<AudioCues>,
+    mut rally: ResMut<RallyCount>,
+    paddles: Query<(&Player, &Transform), With<Paddle>>,
+    mut balls: Query<(&CollidingEntities, &mut Sprite, &mut Velocity, &Transform), With<Ball>>,
 ) {
-    for (hits, mut sprite) in &mut balls {
+    for (hits, mut sprite, mut velocity, ball_transform) in &mut balls {
         for hit in hits.iter() {
-            if let Ok(player) = paddles.get(hit) {
+            if let Ok((player, paddle_transform)) = paddles.get(hit) {
                 sprite.color = player.get_color();
+
+                let offset =
+                    (ball_transform.translation.y - paddle_transform.translation.y) / (PHIGTH / 2.);
+                let theta = offset.clamp(-1., 1.) * MAX_BOUNCE_ANGLE;
+                let dir_x = match player {
+                    Player::Player1 => 1.,
+                    Player::Player2 => -1.,
+                };
+                let speed = (velocity.linvel.length() * BOUNCE_SPEED_GAIN).min(MAX_BALL_SPEED);
+                velocity.linvel = speed * Vec2::new(dir_x * theta.cos(), theta.sin());
+
+                rally.0 += 1;
+                cues.paddle_hit_pitch = Some(rally.pitch());
                 return;
             }
         }
@@ -351,10 +561,12 @@ fn spawn_score(mut commands: Commands) {
         });
 }
 
-#[derive(Default, Resource)]
+#[derive(Default, Resource, Clone)]
 struct Score(HashMap<Player, i32>);
 
 fn score(
+    mut cues: ResMut<AudioCues>,
+    mut rally: ResMut<RallyCount>,
     mut events: EventReader<GameEvents>,
     mut score_text: Query<(&mut Text, &Player)>,
     mut score: ResMut<Score>,
@@ -371,6 +583,8 @@ fn score(
                     text.sections[0].value = score.to_string();
                     break;
                 }
+                rally.reset();
+                cues.score = true;
             }
             GameEvents::ResetBall(_) => {}
         }
@@ -0,0 +1,102 @@
+use std::net::SocketAddr;
+
+use bevy::prelude::Resource;
+use bevy_ggrs::ggrs::{self, PlayerType, SessionBuilder, UdpNonBlockingSocket};
+use bevy_ggrs::Session;
+use clap::Parser;
+
+use crate::input::BoxInput;
+
+/// Ties the GGRS session to our input/state types. `State` is unused by us
+/// (we don't do manual state hashing), but the trait still needs a type.
+pub struct GgrsConfig;
+
+impl ggrs::Config for GgrsConfig {
+    type Input = BoxInput;
+    type State = u8;
+    type Address = SocketAddr;
+}
+
+pub const FPS: usize = 60;
+pub const MAX_PREDICTION: usize = 8;
+pub const INPUT_DELAY: usize = 2;
+
+/// `--players` takes one address per player; `localhost` marks the slot the
+/// current process plays. Mirrors ggrs's own `box_game` example. Pong is
+/// strictly 2-player, so `start_session` rejects anything else.
+#[derive(Parser, Resource, Debug, Clone, Default)]
+#[command(author, version, about = "pong-rs online 1v1")]
+pub struct NetOpt {
+    #[arg(long, default_value_t = 0)]
+    pub local_port: u16,
+    #[arg(long, num_args = 0.., value_delimiter = ' ')]
+    pub players: Vec<String>,
+    /// Run a local `SyncTestSession` instead of real networking, replaying
+    /// each frame `check_distance` times to flag desyncs.
+    #[arg(long)]
+    pub sync_test: bool,
+    /// Play the local match against the tracking AI instead of a second
+    /// keyboard. Ignored once `--players` starts an online session.
+    #[arg(long)]
+    pub vs_ai: bool,
+}
+
+impl NetOpt {
+    pub fn is_online(&self) -> bool {
+        !self.players.is_empty()
+    }
+}
+
+pub fn start_session(opt: &NetOpt) -> Session<GgrsConfig> {
+    let num_players = opt.players.len();
+    assert_eq!(
+        num_players, 2,
+        "--players must list exactly 2 entries (one per paddle), got {num_players}"
+    );
+
+    if opt.sync_test {
+        let mut builder = SessionBuilder::<GgrsConfig>::new()
+            .with_num_players(num_players)
+            .with_check_distance(MAX_PREDICTION);
+
+        for i in 0..num_players {
+            builder = builder
+                .add_player(PlayerType::Local, i)
+                .expect("failed to add local player to synctest session");
+        }
+
+        return Session::SyncTest(
+            builder
+                .start_synctest_session()
+                .expect("failed to start synctest session"),
+        );
+    }
+
+    let mut builder = SessionBuilder::<GgrsConfig>::new()
+        .with_num_players(num_players)
+        .with_input_delay(INPUT_DELAY);
+
+    for (i, player_addr) in opt.players.iter().enumerate() {
+        builder = if player_addr == "localhost" {
+            builder
+                .add_player(PlayerType::Local, i)
+                .expect("failed to add local player")
+        } else {
+            let addr: SocketAddr = player_addr
+                .parse()
+                .expect("--players entries must be `localhost` or `ip:port`");
+            builder
+                .add_player(PlayerType::Remote(addr), i)
+                .expect("failed to add remote player")
+        };
+    }
+
+    let socket = UdpNonBlockingSocket::bind_to_port(opt.local_port)
+        .expect("failed to bind udp socket for GGRS session");
+
+    Session::P2P(
+        builder
+            .start_p2p_session(socket)
+            .expect("failed to start p2p session"),
+    )
+}
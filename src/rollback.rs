@@ -0,0 +1,85 @@
+//! Deterministic counterparts of the local-play systems, scheduled inside
+//! `GgrsSchedule` so GGRS can replay them during a rollback. `ball_hit`,
+//! `reset_ball` and `score` are already pure functions of ECS state and
+//! events, so they're reused as-is; only the systems that previously read
+//! live input/RNG needed rewriting.
+
+use bevy::prelude::*;
+use bevy_ggrs::{PlayerInputs, Rollback};
+use rand::Rng;
+
+use crate::network::{GgrsConfig, FPS};
+use crate::{Ball, GameEvents, MatchRng, Paddle, Player, WINDOW_HIGHT};
+
+/// Fixed per-tick delta matching the rollback schedule's 60 Hz step, used
+/// instead of `Res<Time>` so every peer advances paddles by the same amount.
+const STEP: f32 = 1. / FPS as f32;
+
+pub fn move_paddle_rollback(
+    inputs: Res<PlayerInputs<GgrsConfig>>,
+    mut paddles: Query<(&mut Transform, &Player), (With<Paddle>, With<Rollback>)>,
+) {
+    for (mut pos, player) in &mut paddles {
+        let handle = match player {
+            Player::Player1 => 0,
+            Player::Player2 => 1,
+        };
+        let (input, _) = inputs[handle];
+
+        if input.up() {
+            pos.translation.y += 100. * STEP;
+        }
+        if input.down() {
+            pos.translation.y -= 100. * STEP;
+        }
+        pos.translation.y = pos
+            .translation
+            .y
+            .clamp((-WINDOW_HIGHT / 2.) + 75., (WINDOW_HIGHT / 2.) - 75.);
+    }
+}
+
+pub fn detect_reset_rollback(
+    inputs: Res<PlayerInputs<GgrsConfig>>,
+    balls: Query<&bevy_rapier2d::prelude::CollidingEntities, With<Ball>>,
+    goals: Query<&Player, With<bevy_rapier2d::prelude::Sensor>>,
+    mut rng: ResMut<MatchRng>,
+    mut game_events: EventWriter<GameEvents>,
+) {
+    for (input, _) in inputs.iter() {
+        if input.reset() {
+            let player = if rng.0.gen::<bool>() {
+                Player::Player1
+            } else {
+                Player::Player2
+            };
+            game_events.send(GameEvents::ResetBall(player));
+            return;
+        }
+    }
+
+    for ball in &balls {
+        for hit in ball.iter() {
+            if let Ok(player) = goals.get(hit) {
+                game_events.send(GameEvents::ResetBall(*player));
+                game_events.send(GameEvents::GainPoint(*player));
+            }
+        }
+    }
+}
+
+/// Tags the entities the rollback schedule needs to snapshot. Runs once at
+/// `Startup`, after the shared `spawn_players`/`spawn_ball` systems, only
+/// when an online session is active.
+pub fn assign_rollback(
+    mut commands: Commands,
+    paddles: Query<Entity, With<Paddle>>,
+    balls: Query<Entity, With<Ball>>,
+) {
+    for entity in &paddles {
+        commands.entity(entity).add_rollback();
+    }
+    for entity in &balls {
+        commands.entity(entity).add_rollback();
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+
+use crate::{Ball, GameMode, Player, Score};
+
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    Menu,
+    Playing,
+    GameOver,
+}
+
+/// First player to reach this many points wins the match.
+#[derive(Resource, Clone, Copy)]
+pub struct WinScore(pub i32);
+
+impl Default for WinScore {
+    fn default() -> Self {
+        Self(5)
+    }
+}
+
+/// Set by `check_win` right before it transitions to `GameOver`, and read
+/// back by `spawn_game_over` to render "Player N wins".
+#[derive(Resource, Default)]
+pub struct MatchResult(pub Option<Player>);
+
+/// Tags every entity that belongs to a single match (paddles, ball,
+/// borders) so `despawn_match` can clear the field between games without
+/// touching persistent UI like the score counters.
+#[derive(Component)]
+pub struct InMatch;
+
+#[derive(Component)]
+struct MenuUi;
+
+#[derive(Component)]
+struct MenuModeLabel;
+
+#[derive(Component)]
+struct GameOverUi;
+
+pub fn despawn_match(mut commands: Commands, entities: Query<Entity, With<InMatch>>) {
+    for entity in &entities {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Runs on entering `Playing`, after the match entities are spawned:
+/// clears the point totals from any previous game and puts the counters
+/// back to "0".
+pub fn reset_score(mut score: ResMut<Score>, mut score_text: Query<(&mut Text, &Player)>) {
+    score.0.clear();
+    for (mut text, _) in &mut score_text {
+        text.sections[0].value = "0".to_string();
+    }
+}
+
+pub fn check_win(
+    score: Res<Score>,
+    win_score: Res<WinScore>,
+    mut balls: Query<&mut Velocity, With<Ball>>,
+    mut match_result: ResMut<MatchResult>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let winner = score
+        .0
+        .iter()
+        .find(|(_, points)| **points >= win_score.0)
+        .map(|(player, _)| *player);
+
+    let Some(player) = winner else {
+        return;
+    };
+
+    for mut velocity in &mut balls {
+        *velocity = Velocity::zero();
+    }
+    match_result.0 = Some(player);
+    next_state.set(AppState::GameOver);
+}
+
+fn mode_label(mode: GameMode) -> &'static str {
+    match mode {
+        GameMode::HumanVsHuman => "Mode: Human vs Human",
+        GameMode::HumanVsAi => "Mode: Human vs AI",
+        GameMode::Breakout => "Mode: Breakout",
+    }
+}
+
+pub fn spawn_menu(mut commands: Commands, mode: Res<GameMode>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            MenuUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "PONG",
+                TextStyle {
+                    font_size: 80.,
+                    ..Default::default()
+                },
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    mode_label(*mode),
+                    TextStyle {
+                        font_size: 30.,
+                        ..Default::default()
+                    },
+                ),
+                MenuModeLabel,
+            ));
+            parent.spawn(TextBundle::from_section(
+                "1: Human vs Human   2: Human vs AI   3: Breakout   Enter: Start",
+                TextStyle {
+                    font_size: 24.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_menu(mut commands: Commands, ui: Query<Entity, With<MenuUi>>) {
+    for entity in &ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn handle_menu_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut mode: ResMut<GameMode>,
+    mut labels: Query<&mut Text, With<MenuModeLabel>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let picked = if input.just_pressed(KeyCode::Digit1) {
+        Some(GameMode::HumanVsHuman)
+    } else if input.just_pressed(KeyCode::Digit2) {
+        Some(GameMode::HumanVsAi)
+    } else if input.just_pressed(KeyCode::Digit3) {
+        Some(GameMode::Breakout)
+    } else {
+        None
+    };
+
+    if let Some(picked) = picked {
+        *mode = picked;
+        for mut text in &mut labels {
+            text.sections[0].value = mode_label(*mode).to_string();
+        }
+    }
+
+    if input.just_pressed(KeyCode::Enter) {
+        next_state.set(AppState::Playing);
+    }
+}
+
+pub fn spawn_game_over(mut commands: Commands, match_result: Res<MatchResult>) {
+    let winner_label = match match_result.0 {
+        Some(Player::Player1) => "Player 1 wins!",
+        Some(Player::Player2) => "Player 2 wins!",
+        None => "Game over",
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+                ..Default::default()
+            },
+            GameOverUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                winner_label,
+                TextStyle {
+                    font_size: 60.,
+                    ..Default::default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Press Enter to return to the menu",
+                TextStyle {
+                    font_size: 24.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_game_over(mut commands: Commands, ui: Query<Entity, With<GameOverUi>>) {
+    for entity in &ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn handle_restart_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if input.just_pressed(KeyCode::Enter) {
+        next_state.set(AppState::Menu);
+    }
+}